@@ -0,0 +1,78 @@
+//! Tauri commands for toggling debug logger settings live while attached
+//!
+//! The toggles only do anything when `NOVA_DEBUG=1` started a `DebugState`;
+//! if debug mode isn't running there's nothing to toggle.
+//! `debug_list_sessions` works either way.
+
+use super::{instrument, DebugFilter, DebugState, TmuxSession, Verbosity};
+use serde_json::json;
+use tauri::{AppHandle, Manager, State};
+
+/// Replace the include/exclude filter over command and event names. Uses
+/// the same syntax as `NOVA_DEBUG_FILTER` (comma-separated globs, `!` to
+/// exclude).
+#[tauri::command]
+pub async fn debug_set_filter(state: State<'_, DebugState>, spec: String) -> Result<(), String> {
+    instrument(&state, "debug_set_filter", json!({ "spec": spec }), async {
+        let mut logger = state.logger.lock().await;
+        logger.set_filter(DebugFilter::parse(&spec));
+        Ok(())
+    })
+    .await
+}
+
+/// Set how much of a payload body gets logged: `"compact"` for
+/// `command (Nms)` lines only, `"full"` for the (possibly truncated) body.
+#[tauri::command]
+pub async fn debug_set_verbosity(
+    state: State<'_, DebugState>,
+    verbosity: String,
+) -> Result<(), String> {
+    instrument(
+        &state,
+        "debug_set_verbosity",
+        json!({ "verbosity": verbosity }),
+        async {
+            let verbosity = match verbosity.as_str() {
+                "compact" => Verbosity::Compact,
+                "full" => Verbosity::Full,
+                other => {
+                    return Err(format!("unknown verbosity '{}', expected compact|full", other))
+                }
+            };
+
+            let mut logger = state.logger.lock().await;
+            logger.set_verbosity(verbosity);
+            Ok(())
+        },
+    )
+    .await
+}
+
+/// Toggle quiet mode, which keeps only error records
+#[tauri::command]
+pub async fn debug_set_quiet(state: State<'_, DebugState>, quiet: bool) -> Result<(), String> {
+    instrument(&state, "debug_set_quiet", json!({ "quiet": quiet }), async {
+        let mut logger = state.logger.lock().await;
+        logger.set_quiet(quiet);
+        Ok(())
+    })
+    .await
+}
+
+/// List running `nova-debug-*` tmux sessions, so a developer juggling
+/// several project windows can pick the right one to attach to. Useful
+/// whether or not debug mode is running in this process, so it only
+/// traces through `instrument` when a `DebugState` happens to be managed.
+#[tauri::command]
+pub async fn debug_list_sessions(app: AppHandle) -> Result<Vec<String>, String> {
+    match app.try_state::<DebugState>() {
+        Some(state) => {
+            instrument(&state, "debug_list_sessions", json!({}), async {
+                TmuxSession::list(super::DEBUG_SESSION_NAME)
+            })
+            .await
+        }
+        None => TmuxSession::list(super::DEBUG_SESSION_NAME),
+    }
+}
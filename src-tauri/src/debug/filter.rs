@@ -0,0 +1,101 @@
+//! Include/exclude glob filtering over command and event names
+//!
+//! Seeded from `NOVA_DEBUG_FILTER`, a comma-separated list of glob patterns
+//! (`*` matches any run of characters). A pattern prefixed with `!` is an
+//! exclude pattern and always wins; with no include patterns given,
+//! everything not excluded matches. Example:
+//! `NOVA_DEBUG_FILTER="session_*,!session_heartbeat"` traces every
+//! `session_*` command except the noisy heartbeat one.
+
+/// Include/exclude glob filter over command and event names
+#[derive(Debug, Clone, Default)]
+pub struct DebugFilter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl DebugFilter {
+    /// A filter that matches everything
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Build a filter from a `NOVA_DEBUG_FILTER`-style pattern list
+    pub fn parse(spec: &str) -> Self {
+        let mut include = Vec::new();
+        let mut exclude = Vec::new();
+
+        for pattern in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            if let Some(pattern) = pattern.strip_prefix('!') {
+                exclude.push(pattern.to_string());
+            } else {
+                include.push(pattern.to_string());
+            }
+        }
+
+        Self { include, exclude }
+    }
+
+    /// Build a filter from the `NOVA_DEBUG_FILTER` environment variable, or
+    /// a filter matching everything if it isn't set.
+    pub fn from_env() -> Self {
+        std::env::var("NOVA_DEBUG_FILTER")
+            .map(|spec| Self::parse(&spec))
+            .unwrap_or_default()
+    }
+
+    /// Whether `name` should be logged under this filter
+    pub fn matches(&self, name: &str) -> bool {
+        if self.exclude.iter().any(|p| glob_match(p, name)) {
+            return false;
+        }
+
+        self.include.is_empty() || self.include.iter().any(|p| glob_match(p, name))
+    }
+}
+
+/// Minimal glob matcher supporting `*` as "match any run of characters".
+/// No `?`, character classes, or escaping — patterns here are command and
+/// event names, not file paths.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    fn inner(pattern: &[u8], text: &[u8]) -> bool {
+        match pattern.first() {
+            None => text.is_empty(),
+            Some(b'*') => {
+                inner(&pattern[1..], text) || (!text.is_empty() && inner(pattern, &text[1..]))
+            }
+            Some(&c) => text.first() == Some(&c) && inner(&pattern[1..], &text[1..]),
+        }
+    }
+
+    inner(pattern.as_bytes(), text.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match_wildcard() {
+        assert!(glob_match("session_*", "session_create"));
+        assert!(!glob_match("session_*", "agent_create"));
+        assert!(glob_match("*", "anything"));
+        assert!(glob_match("exact", "exact"));
+        assert!(!glob_match("exact", "exactly"));
+    }
+
+    #[test]
+    fn test_filter_exclude_wins() {
+        let filter = DebugFilter::parse("session_*,!session_heartbeat");
+        assert!(filter.matches("session_create"));
+        assert!(!filter.matches("session_heartbeat"));
+        assert!(!filter.matches("agent_list"));
+    }
+
+    #[test]
+    fn test_filter_empty_matches_everything_except_excluded() {
+        let filter = DebugFilter::parse("!noisy_event");
+        assert!(filter.matches("anything"));
+        assert!(!filter.matches("noisy_event"));
+    }
+}
@@ -0,0 +1,62 @@
+//! Opt-in per-command instrumentation for Tauri commands
+//!
+//! This is deliberately not dispatch-level middleware: wrapping the
+//! closure from `tauri::generate_handler!` only has access to the raw IPC
+//! message, not the command's resolved `Result` (there's no public way to
+//! hook the generated `InvokeResolver`'s eventual `resolve`/`reject`), so
+//! a dispatch wrapper could log an invoke but never its response or
+//! error. `instrument` is called from inside each command body instead:
+//! it logs the invoke, awaits the body, then logs the correlated response
+//! or error under the same request id. A command that doesn't call it
+//! isn't traced — this is an opt-in tracer per command, not an automatic
+//! one for the whole backend.
+
+use super::DebugState;
+use serde_json::Value;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+/// Monotonically increasing id correlating an invoke with its response
+static NEXT_REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Allocate the next request id
+pub fn next_request_id() -> u64 {
+    NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Log `command`'s invoke, run `fut`, then log its response or error under
+/// the same request id, so a debug sink can correlate the pair.
+pub async fn instrument<T, E, F>(
+    state: &DebugState,
+    command: &str,
+    params: Value,
+    fut: F,
+) -> Result<T, E>
+where
+    F: Future<Output = Result<T, E>>,
+    T: serde::Serialize,
+    E: std::fmt::Display,
+{
+    let request_id = next_request_id();
+
+    {
+        let logger = state.logger.lock().await;
+        logger.log_invoke(command, &params, request_id);
+    }
+
+    let start = Instant::now();
+    let result = fut.await;
+    let duration_ms = start.elapsed().as_millis() as u64;
+
+    let logger = state.logger.lock().await;
+    match &result {
+        Ok(value) => {
+            let value = serde_json::to_value(value).unwrap_or(Value::Null);
+            logger.log_response(command, &value, duration_ms, request_id);
+        }
+        Err(e) => logger.log_error(command, &e.to_string(), request_id),
+    }
+
+    result
+}
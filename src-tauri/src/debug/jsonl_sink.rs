@@ -0,0 +1,130 @@
+//! JSONL audit sink: one JSON object per debug record, appended to a file
+//!
+//! This gives a debug run a durable trail that survives detaching from
+//! tmux and can be grepped or replayed after the app exits.
+
+use super::sink::{DebugRecord, DebugSink};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Appends one JSON object per line to an audit log file
+pub struct JsonlSink {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl JsonlSink {
+    /// Open (or create) the audit log at `path` for appending
+    pub fn new(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| format!("Failed to open audit log {}: {}", path.display(), e))?;
+
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    fn append(&self, record: &DebugRecord) {
+        let line = match serde_json::to_string(record) {
+            Ok(line) => line,
+            Err(e) => {
+                log::warn!("Failed to serialize debug record: {}", e);
+                return;
+            }
+        };
+
+        let mut file = match self.file.lock() {
+            Ok(file) => file,
+            Err(e) => {
+                log::warn!("Audit log mutex poisoned: {}", e);
+                return;
+            }
+        };
+
+        if let Err(e) = writeln!(file, "{}", line) {
+            log::warn!("Failed to append to audit log {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+impl DebugSink for JsonlSink {
+    fn log_invoke(&self, record: &DebugRecord) {
+        self.append(record);
+    }
+
+    fn log_response(&self, record: &DebugRecord) {
+        self.append(record);
+    }
+
+    fn log_event(&self, record: &DebugRecord) {
+        self.append(record);
+    }
+
+    fn log_error(&self, record: &DebugRecord) {
+        self.append(record);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use std::io::{BufRead, BufReader};
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "nova-debug-jsonl-sink-test-{}-{}.jsonl",
+            std::process::id(),
+            name
+        ))
+    }
+
+    #[test]
+    fn round_trips_a_record_as_one_json_line() {
+        let path = temp_path("round-trip");
+        let sink = JsonlSink::new(&path).expect("open audit log");
+
+        let record = DebugRecord::invoke("debug_set_quiet", json!({ "quiet": true }), Some(7));
+        sink.log_invoke(&record);
+        drop(sink);
+
+        let file = File::open(&path).expect("read audit log");
+        let lines: Vec<String> = BufReader::new(file)
+            .lines()
+            .collect::<Result<_, _>>()
+            .expect("read lines");
+        assert_eq!(lines.len(), 1);
+
+        let parsed: Value = serde_json::from_str(&lines[0]).expect("parse json line");
+        assert_eq!(parsed["command"], "debug_set_quiet");
+        assert_eq!(parsed["request_id"], 7);
+        assert_eq!(parsed["params"], json!({ "quiet": true }));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn appends_across_multiple_records() {
+        let path = temp_path("append");
+        let sink = JsonlSink::new(&path).expect("open audit log");
+
+        sink.log_invoke(&DebugRecord::invoke("cmd", Value::Null, Some(1)));
+        sink.log_response(&DebugRecord::response("cmd", Value::Null, 12, Some(1)));
+
+        let file = File::open(&path).expect("read audit log");
+        let lines: Vec<String> = BufReader::new(file)
+            .lines()
+            .collect::<Result<_, _>>()
+            .expect("read lines");
+        assert_eq!(lines.len(), 2);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,144 @@
+//! Live sink: renders debug records as ASCII-boxed text over a [`DebugTransport`]
+//!
+//! This is the original rendering `DebugLogger` used to do directly against
+//! a tmux pipe, now factored out behind the `DebugSink` trait and
+//! parameterized over whichever transport `select_transport` picked, so the
+//! same rendering works whether the other end is a tmux pane or a socket
+//! client.
+//!
+//! A record whose body was dropped by `Verbosity::Compact` renders as a
+//! single `>>> command` / `<<< command (Nms)` line instead of a box.
+
+use super::sink::{DebugRecord, DebugSink, RecordKind};
+use super::transport::DebugTransport;
+use serde_json::Value;
+
+/// Writes formatted debug records to a [`DebugTransport`]
+pub struct LiveSink {
+    transport: Box<dyn DebugTransport>,
+}
+
+impl LiveSink {
+    /// Wrap a transport, writing a welcome header immediately.
+    pub fn new(transport: Box<dyn DebugTransport>, session_name: &str) -> Result<Self, String> {
+        let sink = Self { transport };
+        sink.write_header(session_name)?;
+        Ok(sink)
+    }
+
+    fn write_header(&self, session_name: &str) -> Result<(), String> {
+        let header = format!(
+            r#"
+╔══════════════════════════════════════════════════════════════════════════════╗
+║                         OPCODE DEBUG MODE                                     ║
+║                                                                              ║
+║  Session: {}                                                  ║
+║  Started: {}                                           ║
+║                                                                              ║
+║  Legend:                                                                     ║
+║    >>> INVOKE   - Frontend calling backend command                           ║
+║    <<< RESPONSE - Backend returning result                                   ║
+║    --> EVENT    - Backend emitting event to frontend                         ║
+║    !!! ERROR    - Error occurred                                             ║
+╚══════════════════════════════════════════════════════════════════════════════╝
+"#,
+            session_name,
+            chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
+        );
+
+        self.transport.write(&header)
+    }
+
+    fn format_json(value: &Value) -> String {
+        serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+    }
+
+    fn write(&self, msg: String) {
+        if let Err(e) = self.transport.write(&msg) {
+            log::warn!("Failed to write debug log: {}", e);
+        }
+    }
+}
+
+impl DebugSink for LiveSink {
+    fn log_invoke(&self, record: &DebugRecord) {
+        let Some(params) = record.params.as_ref() else {
+            // Compact verbosity dropped the body: just note the call happened.
+            self.write(format!(">>> {}", record.command));
+            return;
+        };
+
+        let msg = format!(
+            r#"
+════════════════════════════════════════════════════════════════════════════════
+[{}] >>> INVOKE: {}
+────────────────────────────────────────────────────────────────────────────────
+{}
+════════════════════════════════════════════════════════════════════════════════"#,
+            record.ts.format("%Y-%m-%dT%H:%M:%S%.3fZ"),
+            record.command,
+            Self::format_json(params)
+        );
+        self.write(msg);
+    }
+
+    fn log_response(&self, record: &DebugRecord) {
+        let duration_ms = record.duration_ms.unwrap_or_default();
+
+        let Some(result) = record.result.as_ref() else {
+            self.write(format!("<<< {} ({}ms)", record.command, duration_ms));
+            return;
+        };
+
+        let msg = format!(
+            r#"
+════════════════════════════════════════════════════════════════════════════════
+[{}] <<< RESPONSE: {} ({}ms)
+────────────────────────────────────────────────────────────────────────────────
+{}
+════════════════════════════════════════════════════════════════════════════════"#,
+            record.ts.format("%Y-%m-%dT%H:%M:%S%.3fZ"),
+            record.command,
+            duration_ms,
+            Self::format_json(result)
+        );
+        self.write(msg);
+    }
+
+    fn log_event(&self, record: &DebugRecord) {
+        debug_assert_eq!(record.kind, RecordKind::Event);
+
+        let Some(params) = record.params.as_ref() else {
+            self.write(format!("--> {}", record.command));
+            return;
+        };
+
+        let msg = format!(
+            r#"
+════════════════════════════════════════════════════════════════════════════════
+[{}] --> EVENT: {}
+────────────────────────────────────────────────────────────────────────────────
+{}
+════════════════════════════════════════════════════════════════════════════════"#,
+            record.ts.format("%Y-%m-%dT%H:%M:%S%.3fZ"),
+            record.command,
+            Self::format_json(params)
+        );
+        self.write(msg);
+    }
+
+    fn log_error(&self, record: &DebugRecord) {
+        let msg = format!(
+            r#"
+════════════════════════════════════════════════════════════════════════════════
+[{}] !!! ERROR: {}
+────────────────────────────────────────────────────────────────────────────────
+{}
+════════════════════════════════════════════════════════════════════════════════"#,
+            record.ts.format("%Y-%m-%dT%H:%M:%S%.3fZ"),
+            record.command,
+            record.error.as_deref().unwrap_or_default()
+        );
+        self.write(msg);
+    }
+}
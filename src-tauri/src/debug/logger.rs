@@ -1,65 +1,135 @@
-//! Debug logger for formatting and writing debug messages
+//! Debug logger that fans structured records out to a live view and a set
+//! of durable audit sinks
 //!
-//! Provides formatted output for Tauri command invocations, responses,
-//! and streaming events.
+//! `DebugLogger` no longer renders text itself; it builds a [`DebugRecord`]
+//! for each call and hands it to the live sink and every registered audit
+//! sink. The tmux renderer and the audit sinks all implement the same
+//! [`DebugSink`] trait, so adding a new destination doesn't touch this
+//! file.
+//!
+//! [`DebugFilter`], [`Verbosity`], and quiet mode exist to keep the *live*
+//! view readable — they're applied only on the path to the live sink.
+//! Audit sinks (the JSONL/SQLite trail meant to be grepped or queried
+//! after the app exits) always receive the full, unfiltered record, so
+//! toggling live-view noise down never costs you data in the durable
+//! trail.
 
-use super::tmux::TmuxSession;
-use chrono::{DateTime, Utc};
+use super::filter::DebugFilter;
+use super::live_sink::LiveSink;
+use super::sink::{DebugRecord, DebugSink};
+use super::transport::{select_transport, TransportInfo};
 use serde_json::Value;
 
-/// Debug logger that writes formatted messages to a tmux session
+/// How much of a payload body the *live view* carries. Audit sinks are
+/// unaffected — see the module docs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verbosity {
+    /// Drop payload bodies entirely; the live view renders just
+    /// `command (Nms)`.
+    Compact,
+    /// Carry the full payload, truncated if it exceeds
+    /// [`Verbosity::TRUNCATE_THRESHOLD_BYTES`].
+    Full,
+}
+
+impl Verbosity {
+    /// JSON payloads larger than this are replaced with a placeholder
+    const TRUNCATE_THRESHOLD_BYTES: usize = 4096;
+
+    fn shape(self, value: Value) -> Option<Value> {
+        match self {
+            Verbosity::Compact => None,
+            Verbosity::Full => {
+                let rendered = value.to_string();
+                if rendered.len() > Self::TRUNCATE_THRESHOLD_BYTES {
+                    Some(Value::String(format!(
+                        "<truncated {} bytes>",
+                        rendered.len()
+                    )))
+                } else {
+                    Some(value)
+                }
+            }
+        }
+    }
+}
+
+impl Default for Verbosity {
+    fn default() -> Self {
+        Verbosity::Full
+    }
+}
+
+/// Debug logger that writes structured records to a live view and any
+/// number of durable audit sinks
 pub struct DebugLogger {
-    tmux: TmuxSession,
+    live: Box<dyn DebugSink>,
+    audit: Vec<Box<dyn DebugSink>>,
+    filter: DebugFilter,
+    verbosity: Verbosity,
+    quiet: bool,
 }
 
 impl DebugLogger {
-    /// Create a new debug logger with a tmux session
+    /// Create a new debug logger backed by the live view only, picking a
+    /// transport automatically (see [`select_transport`]).
     ///
     /// # Arguments
-    /// * `session_name` - Name for the tmux session
+    /// * `session_name` - Name for the debug session
     pub fn new(session_name: &str) -> Result<Self, String> {
-        let tmux = TmuxSession::create(session_name)?;
-        let logger = Self { tmux };
+        let (logger, _info) = Self::new_with_transport(session_name)?;
+        Ok(logger)
+    }
 
-        // Write welcome message
-        logger.write_header()?;
+    /// Like [`DebugLogger::new`], but also returns which transport was
+    /// selected so callers (e.g. `DebugState`) can surface it.
+    pub fn new_with_transport(session_name: &str) -> Result<(Self, TransportInfo), String> {
+        let (transport, info) = select_transport(session_name)?;
+        let live: Box<dyn DebugSink> = Box::new(LiveSink::new(transport, session_name)?);
+        Ok((Self::with_live_sink(live), info))
+    }
 
-        Ok(logger)
+    /// Create a debug logger from an explicit live sink, with no audit
+    /// sinks attached yet.
+    pub fn with_live_sink(live: Box<dyn DebugSink>) -> Self {
+        Self {
+            live,
+            audit: Vec::new(),
+            filter: DebugFilter::from_env(),
+            verbosity: Verbosity::default(),
+            quiet: false,
+        }
     }
 
-    /// Write the initial header message
-    fn write_header(&self) -> Result<(), String> {
-        let header = format!(
-            r#"
-╔══════════════════════════════════════════════════════════════════════════════╗
-║                         OPCODE DEBUG MODE                                     ║
-║                                                                              ║
-║  Session: {}                                                  ║
-║  Started: {}                                           ║
-║                                                                              ║
-║  Legend:                                                                     ║
-║    >>> INVOKE   - Frontend calling backend command                           ║
-║    <<< RESPONSE - Backend returning result                                   ║
-║    --> EVENT    - Backend emitting event to frontend                         ║
-║    !!! ERROR    - Error occurred                                             ║
-╚══════════════════════════════════════════════════════════════════════════════╝
-"#,
-            self.tmux.session_name(),
-            Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
-        );
+    /// Register an additional audit sink. Unlike the live sink, audit
+    /// sinks always receive the full record regardless of filter,
+    /// verbosity, or quiet mode.
+    pub fn add_audit_sink(&mut self, sink: Box<dyn DebugSink>) {
+        self.audit.push(sink);
+    }
 
-        self.tmux.write(&header)
+    /// Replace the include/exclude filter over command and event names.
+    /// Affects only the live view.
+    pub fn set_filter(&mut self, filter: DebugFilter) {
+        self.filter = filter;
     }
 
-    /// Get current timestamp
-    fn timestamp() -> String {
-        let now: DateTime<Utc> = Utc::now();
-        now.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()
+    /// Set how much of a payload body the live view renders. Affects only
+    /// the live view.
+    pub fn set_verbosity(&mut self, verbosity: Verbosity) {
+        self.verbosity = verbosity;
     }
 
-    /// Format JSON value for display (pretty print with indentation)
-    fn format_json(value: &Value) -> String {
-        serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+    /// When `true`, suppress invoke/response/event records on the live
+    /// view and keep only errors, regardless of the filter. Affects only
+    /// the live view.
+    pub fn set_quiet(&mut self, quiet: bool) {
+        self.quiet = quiet;
+    }
+
+    /// Whether the live view should see this name right now
+    fn live_wants(&self, name: &str) -> bool {
+        !self.quiet && self.filter.matches(name)
     }
 
     /// Log a command invocation from frontend to backend
@@ -67,22 +137,20 @@ impl DebugLogger {
     /// # Arguments
     /// * `command` - Name of the Tauri command
     /// * `params` - Parameters passed to the command
-    pub fn log_invoke(&self, command: &str, params: &Value) {
-        let msg = format!(
-            r#"
-════════════════════════════════════════════════════════════════════════════════
-[{}] >>> INVOKE: {}
-────────────────────────────────────────────────────────────────────────────────
-{}
-════════════════════════════════════════════════════════════════════════════════"#,
-            Self::timestamp(),
-            command,
-            Self::format_json(params)
-        );
-
-        if let Err(e) = self.tmux.write(&msg) {
-            log::warn!("Failed to write debug log: {}", e);
+    /// * `request_id` - Id correlating this invoke with its eventual
+    ///   response/error record
+    pub fn log_invoke(&self, command: &str, params: &Value, request_id: u64) {
+        let audit_record = DebugRecord::invoke(command, params.clone(), Some(request_id));
+        for sink in &self.audit {
+            sink.log_invoke(&audit_record);
+        }
+
+        if !self.live_wants(command) {
+            return;
         }
+        let mut live_record = DebugRecord::invoke(command, Value::Null, Some(request_id));
+        live_record.params = self.verbosity.shape(params.clone());
+        self.live.log_invoke(&live_record);
     }
 
     /// Log a command response from backend to frontend
@@ -91,23 +159,21 @@ impl DebugLogger {
     /// * `command` - Name of the Tauri command
     /// * `result` - Result returned by the command
     /// * `duration_ms` - Execution time in milliseconds
-    pub fn log_response(&self, command: &str, result: &Value, duration_ms: u64) {
-        let msg = format!(
-            r#"
-════════════════════════════════════════════════════════════════════════════════
-[{}] <<< RESPONSE: {} ({}ms)
-────────────────────────────────────────────────────────────────────────────────
-{}
-════════════════════════════════════════════════════════════════════════════════"#,
-            Self::timestamp(),
-            command,
-            duration_ms,
-            Self::format_json(result)
-        );
-
-        if let Err(e) = self.tmux.write(&msg) {
-            log::warn!("Failed to write debug log: {}", e);
+    /// * `request_id` - Id correlating this response with the invoke that
+    ///   triggered it
+    pub fn log_response(&self, command: &str, result: &Value, duration_ms: u64, request_id: u64) {
+        let audit_record =
+            DebugRecord::response(command, result.clone(), duration_ms, Some(request_id));
+        for sink in &self.audit {
+            sink.log_response(&audit_record);
         }
+
+        if !self.live_wants(command) {
+            return;
+        }
+        let mut live_record = DebugRecord::response(command, Value::Null, duration_ms, Some(request_id));
+        live_record.result = self.verbosity.shape(result.clone());
+        self.live.log_response(&live_record);
     }
 
     /// Log an event emitted from backend to frontend
@@ -116,50 +182,132 @@ impl DebugLogger {
     /// * `event` - Name of the event
     /// * `payload` - Event payload
     pub fn log_event(&self, event: &str, payload: &Value) {
-        let msg = format!(
-            r#"
-════════════════════════════════════════════════════════════════════════════════
-[{}] --> EVENT: {}
-────────────────────────────────────────────────────────────────────────────────
-{}
-════════════════════════════════════════════════════════════════════════════════"#,
-            Self::timestamp(),
-            event,
-            Self::format_json(payload)
-        );
-
-        if let Err(e) = self.tmux.write(&msg) {
-            log::warn!("Failed to write debug log: {}", e);
+        let audit_record = DebugRecord::event(event, payload.clone());
+        for sink in &self.audit {
+            sink.log_event(&audit_record);
+        }
+
+        if !self.live_wants(event) {
+            return;
         }
+        let mut live_record = DebugRecord::event(event, Value::Null);
+        live_record.params = self.verbosity.shape(payload.clone());
+        self.live.log_event(&live_record);
     }
 
-    /// Log an error
+    /// Log an error. Always sent to every audit sink and, on the live
+    /// view, always logged regardless of quiet mode; still subject to the
+    /// command/event name filter on the live view.
     ///
     /// # Arguments
     /// * `command` - Name of the command that failed
     /// * `error` - Error message
-    pub fn log_error(&self, command: &str, error: &str) {
-        let msg = format!(
-            r#"
-════════════════════════════════════════════════════════════════════════════════
-[{}] !!! ERROR: {}
-────────────────────────────────────────────────────────────────────────────────
-{}
-════════════════════════════════════════════════════════════════════════════════"#,
-            Self::timestamp(),
-            command,
-            error
-        );
-
-        if let Err(e) = self.tmux.write(&msg) {
-            log::warn!("Failed to write debug log: {}", e);
+    /// * `request_id` - Id correlating this error with the invoke that
+    ///   triggered it
+    pub fn log_error(&self, command: &str, error: &str, request_id: u64) {
+        let audit_record = DebugRecord::error(command, error, Some(request_id));
+        for sink in &self.audit {
+            sink.log_error(&audit_record);
+        }
+
+        if !self.filter.matches(command) {
+            return;
         }
+        self.live.log_error(&audit_record);
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    /// Captures every record handed to it, so a test can inspect exactly
+    /// what a sink was sent.
+    #[derive(Default)]
+    struct RecordingSink {
+        records: Mutex<Vec<DebugRecord>>,
+    }
+
+    impl DebugSink for Arc<RecordingSink> {
+        fn log_invoke(&self, record: &DebugRecord) {
+            self.records.lock().unwrap().push(record.clone());
+        }
+        fn log_response(&self, record: &DebugRecord) {
+            self.records.lock().unwrap().push(record.clone());
+        }
+        fn log_event(&self, record: &DebugRecord) {
+            self.records.lock().unwrap().push(record.clone());
+        }
+        fn log_error(&self, record: &DebugRecord) {
+            self.records.lock().unwrap().push(record.clone());
+        }
+    }
+
+    #[test]
+    fn test_compact_verbosity_drops_payload_instead_of_nulling_it() {
+        let sink = Arc::new(RecordingSink::default());
+        let mut logger = DebugLogger::with_live_sink(Box::new(sink.clone()));
+        logger.set_verbosity(Verbosity::Compact);
+
+        logger.log_invoke("debug_set_quiet", &json!({ "quiet": true }), 1);
+
+        let recorded = sink.records.lock().unwrap();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].params, None);
+    }
+
+    #[test]
+    fn test_full_verbosity_carries_payload() {
+        let sink = Arc::new(RecordingSink::default());
+        let mut logger = DebugLogger::with_live_sink(Box::new(sink.clone()));
+        logger.set_verbosity(Verbosity::Full);
 
-    /// Log a raw message without formatting
-    pub fn log_raw(&self, msg: &str) {
-        if let Err(e) = self.tmux.write(msg) {
-            log::warn!("Failed to write debug log: {}", e);
+        logger.log_invoke("debug_set_quiet", &json!({ "quiet": true }), 1);
+
+        let recorded = sink.records.lock().unwrap();
+        assert_eq!(recorded[0].params, Some(json!({ "quiet": true })));
+    }
+
+    #[test]
+    fn test_full_verbosity_truncates_oversized_payload() {
+        let large = Value::String("x".repeat(Verbosity::TRUNCATE_THRESHOLD_BYTES + 1));
+        let shaped = Verbosity::Full.shape(large);
+        match shaped {
+            Some(Value::String(s)) => assert!(s.starts_with("<truncated")),
+            other => panic!("expected a truncation placeholder, got {:?}", other),
         }
     }
+
+    #[test]
+    fn test_compact_verbosity_and_quiet_do_not_affect_audit_sinks() {
+        let live = Arc::new(RecordingSink::default());
+        let audit = Arc::new(RecordingSink::default());
+        let mut logger = DebugLogger::with_live_sink(Box::new(live.clone()));
+        logger.add_audit_sink(Box::new(audit.clone()));
+        logger.set_verbosity(Verbosity::Compact);
+        logger.set_quiet(true);
+
+        logger.log_invoke("debug_set_quiet", &json!({ "quiet": true }), 1);
+
+        assert_eq!(live.records.lock().unwrap().len(), 0);
+        let audit_records = audit.records.lock().unwrap();
+        assert_eq!(audit_records.len(), 1);
+        assert_eq!(audit_records[0].params, Some(json!({ "quiet": true })));
+    }
+
+    #[test]
+    fn test_filter_does_not_affect_audit_sinks() {
+        let live = Arc::new(RecordingSink::default());
+        let audit = Arc::new(RecordingSink::default());
+        let mut logger = DebugLogger::with_live_sink(Box::new(live.clone()));
+        logger.add_audit_sink(Box::new(audit.clone()));
+        logger.set_filter(DebugFilter::parse("!debug_set_quiet"));
+
+        logger.log_invoke("debug_set_quiet", &json!({ "quiet": true }), 1);
+
+        assert_eq!(live.records.lock().unwrap().len(), 0);
+        assert_eq!(audit.records.lock().unwrap().len(), 1);
+    }
 }
@@ -1,23 +1,52 @@
 //! Debug mode module for Nova
 //!
-//! Provides a tmux-based debugging proxy that logs all communication
-//! between the frontend and backend.
+//! Provides a debugging proxy that logs all communication between the
+//! frontend and backend to a live view (tmux pane or local socket) plus
+//! optional audit sinks.
 //!
 //! # Activation
 //! Set environment variable `NOVA_DEBUG=1` before starting the app.
+//! Optionally set `NOVA_DEBUG_TRANSPORT=tmux` or `NOVA_DEBUG_TRANSPORT=socket`
+//! to force the live-view transport instead of auto-detecting tmux.
+//!
+//! # Audit sinks
+//! Set `NOVA_DEBUG_AUDIT_LOG=<path>` to additionally append every record as
+//! JSON Lines to `<path>` (grep-able, replayable after the app exits). With
+//! the `sqlite-sink` feature enabled, set `NOVA_DEBUG_AUDIT_DB=<path>` to
+//! additionally persist records to a SQLite `debug_events` table for ad-hoc
+//! SQL (e.g. aggregate invoke latencies by command).
 //!
 //! # Usage
 //! ```bash
 //! NOVA_DEBUG=1 cargo tauri dev
-//! # In another terminal:
+//! # In another terminal, run the attach command DebugState logs. Inside a
+//! # Git repo the session is nova-debug-<repo-dir>-<hash>; with tmux and
+//! # outside a repo it is:
 //! tmux attach -t nova-debug
 //! ```
 
+pub mod commands;
+pub mod filter;
+pub mod instrument;
+pub mod jsonl_sink;
+pub mod live_sink;
 pub mod logger;
+pub mod sink;
+#[cfg(feature = "sqlite-sink")]
+pub mod sqlite_sink;
 pub mod tmux;
+pub mod transport;
 
-pub use logger::DebugLogger;
+pub use filter::DebugFilter;
+pub use instrument::instrument;
+pub use jsonl_sink::JsonlSink;
+pub use live_sink::LiveSink;
+pub use logger::{DebugLogger, Verbosity};
+pub use sink::{DebugRecord, DebugSink, RecordKind};
+#[cfg(feature = "sqlite-sink")]
+pub use sqlite_sink::SqliteSink;
 pub use tmux::TmuxSession;
+pub use transport::{DebugTransport, TransportInfo, TransportKind};
 
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -25,23 +54,120 @@ use tokio::sync::Mutex;
 /// State managed by Tauri for debug mode
 pub struct DebugState {
     pub logger: Arc<Mutex<DebugLogger>>,
+    /// Which live-view transport was selected, plus how to attach to it,
+    /// so the frontend (or a developer reading the log) can find it.
+    pub transport: TransportInfo,
 }
 
 impl DebugState {
-    /// Create a new DebugState with a logger
-    pub fn new(logger: DebugLogger) -> Self {
+    /// Create a new DebugState, selecting a live-view transport for
+    /// `session_name`, logging how to attach to it, and wiring in whichever
+    /// audit sinks are opted into via `NOVA_DEBUG_AUDIT_LOG`/
+    /// `NOVA_DEBUG_AUDIT_DB`.
+    pub fn new(session_name: &str) -> Result<Self, String> {
+        let (mut logger, transport) = DebugLogger::new_with_transport(session_name)?;
+        log::info!(
+            "Debug mode using '{}' transport. Attach with: {}",
+            transport.kind.as_str(),
+            transport.attach_instructions
+        );
+
+        if let Ok(path) = std::env::var("NOVA_DEBUG_AUDIT_LOG") {
+            match JsonlSink::new(&path) {
+                Ok(sink) => {
+                    logger.add_audit_sink(Box::new(sink));
+                    log::info!("Debug audit log enabled at {}", path);
+                }
+                Err(e) => log::warn!("Failed to enable debug audit log: {}", e),
+            }
+        }
+
+        #[cfg(feature = "sqlite-sink")]
+        if let Ok(path) = std::env::var("NOVA_DEBUG_AUDIT_DB") {
+            match SqliteSink::new(&path) {
+                Ok(sink) => {
+                    logger.add_audit_sink(Box::new(sink));
+                    log::info!("Debug audit database enabled at {}", path);
+                }
+                Err(e) => log::warn!("Failed to enable debug audit database: {}", e),
+            }
+        }
+
+        Ok(Self {
+            logger: Arc::new(Mutex::new(logger)),
+            transport,
+        })
+    }
+
+    /// Create a DebugState from an already-built logger (e.g. one with
+    /// extra audit sinks attached), leaving `transport` as given.
+    pub fn from_logger(logger: DebugLogger, transport: TransportInfo) -> Self {
         Self {
             logger: Arc::new(Mutex::new(logger)),
+            transport,
         }
     }
 }
 
-/// Check if debug mode is enabled via NOVA_DEBUG environment variable
+/// Check if debug mode is enabled via the `NOVA_DEBUG` environment variable
 pub fn is_debug_enabled() -> bool {
     std::env::var("NOVA_DEBUG")
         .map(|v| v == "1" || v.to_lowercase() == "true")
         .unwrap_or(false)
 }
 
-/// Default session name for debug mode
+/// Read the `NOVA_DEBUG_TRANSPORT` override (`tmux` or `socket`), if set.
+/// An unrecognized value is treated as unset and `select_transport` falls
+/// back to auto-detection.
+pub fn debug_transport_override() -> Option<String> {
+    std::env::var("NOVA_DEBUG_TRANSPORT")
+        .ok()
+        .filter(|v| v == "tmux" || v == "socket")
+}
+
+/// Default session name for debug mode, used when the working directory
+/// isn't inside a Git repository.
 pub const DEBUG_SESSION_NAME: &str = "nova-debug";
+
+/// Pick the debug session name: `nova-debug-<repo-root-dir>-<short-hash>`
+/// when the current working directory is inside a Git repo, otherwise
+/// [`DEBUG_SESSION_NAME`]. The hash is derived from the repo root's full
+/// path so two checkouts that happen to share a directory name (e.g. two
+/// worktrees both named `nova`) still land on distinct sessions.
+pub fn default_session_name() -> String {
+    match git_repo_root() {
+        Some(root) => {
+            let name = root
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| DEBUG_SESSION_NAME.to_string());
+            format!("{}-{}-{:x}", DEBUG_SESSION_NAME, name, path_hash(&root))
+        }
+        None => DEBUG_SESSION_NAME.to_string(),
+    }
+}
+
+/// Walk up from the current directory looking for a `.git` entry, and
+/// return the directory it was found in.
+fn git_repo_root() -> Option<std::path::PathBuf> {
+    let mut dir = std::env::current_dir().ok()?;
+    loop {
+        if dir.join(".git").exists() {
+            return Some(dir);
+        }
+        if !dir.pop() {
+            return None;
+        }
+    }
+}
+
+/// A short, stable hash of a path, used to disambiguate same-named repo
+/// directories in the debug session name.
+fn path_hash(path: &std::path::Path) -> u32 {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    path.hash(&mut hasher);
+    hasher.finish() as u32
+}
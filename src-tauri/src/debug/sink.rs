@@ -0,0 +1,115 @@
+//! `DebugSink` trait and the structured record it receives
+//!
+//! A sink is anything that can persist or render a debug record. Splitting
+//! the record out as structured data (rather than a pre-formatted string)
+//! lets each sink decide its own representation: the tmux sink still draws
+//! ASCII boxes, while the audit sinks just serialize the record as-is.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+
+/// The kind of event a `DebugRecord` describes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordKind {
+    Invoke,
+    Response,
+    Event,
+    Error,
+}
+
+/// A single structured debug event, handed to every registered sink
+#[derive(Debug, Clone, Serialize)]
+pub struct DebugRecord {
+    pub ts: DateTime<Utc>,
+    pub kind: RecordKind,
+    pub command: String,
+    /// Correlates an invoke with its eventual response/error record. Kept
+    /// as its own field (rather than folded into `command`) so sinks like
+    /// the SQLite one can still `GROUP BY command`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl DebugRecord {
+    pub fn invoke(command: impl Into<String>, params: Value, request_id: Option<u64>) -> Self {
+        Self {
+            ts: Utc::now(),
+            kind: RecordKind::Invoke,
+            command: command.into(),
+            request_id,
+            params: Some(params),
+            result: None,
+            duration_ms: None,
+            error: None,
+        }
+    }
+
+    pub fn response(
+        command: impl Into<String>,
+        result: Value,
+        duration_ms: u64,
+        request_id: Option<u64>,
+    ) -> Self {
+        Self {
+            ts: Utc::now(),
+            kind: RecordKind::Response,
+            command: command.into(),
+            request_id,
+            params: None,
+            result: Some(result),
+            duration_ms: Some(duration_ms),
+            error: None,
+        }
+    }
+
+    pub fn event(event: impl Into<String>, payload: Value) -> Self {
+        Self {
+            ts: Utc::now(),
+            kind: RecordKind::Event,
+            command: event.into(),
+            request_id: None,
+            params: Some(payload),
+            result: None,
+            duration_ms: None,
+            error: None,
+        }
+    }
+
+    pub fn error(
+        command: impl Into<String>,
+        error: impl Into<String>,
+        request_id: Option<u64>,
+    ) -> Self {
+        Self {
+            ts: Utc::now(),
+            kind: RecordKind::Error,
+            command: command.into(),
+            request_id,
+            params: None,
+            result: None,
+            duration_ms: None,
+            error: Some(error.into()),
+        }
+    }
+}
+
+/// A destination for debug records
+///
+/// Implementors should not panic or block the caller for long; `DebugLogger`
+/// fans every record out to all registered sinks synchronously.
+pub trait DebugSink: Send + Sync {
+    fn log_invoke(&self, record: &DebugRecord);
+    fn log_response(&self, record: &DebugRecord);
+    fn log_event(&self, record: &DebugRecord);
+    fn log_error(&self, record: &DebugRecord);
+}
@@ -0,0 +1,157 @@
+//! SQLite audit sink: persists debug records to a `debug_events` table
+//!
+//! Optional alternative to [`JsonlSink`] for when a recorded run needs to be
+//! queried with SQL (e.g. aggregate invoke latencies) rather than grepped.
+//! Gated behind the `sqlite-sink` feature so the default build doesn't pull
+//! in `rusqlite`.
+
+use super::sink::{DebugRecord, DebugSink};
+use rusqlite::{params, Connection};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Appends debug records to a `debug_events` table in a SQLite database
+pub struct SqliteSink {
+    conn: Mutex<Connection>,
+}
+
+impl SqliteSink {
+    /// Open (or create) the database at `path` and ensure the schema exists
+    pub fn new(path: impl AsRef<Path>) -> Result<Self, String> {
+        let conn = Connection::open(path.as_ref())
+            .map_err(|e| format!("Failed to open debug database: {}", e))?;
+
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS debug_events (
+                id          INTEGER PRIMARY KEY AUTOINCREMENT,
+                ts          TEXT NOT NULL,
+                kind        TEXT NOT NULL,
+                command     TEXT NOT NULL,
+                request_id  INTEGER,
+                params      TEXT,
+                result      TEXT,
+                duration_ms INTEGER,
+                error       TEXT
+            );
+            CREATE INDEX IF NOT EXISTS idx_debug_events_ts ON debug_events (ts);
+            CREATE INDEX IF NOT EXISTS idx_debug_events_command ON debug_events (command);
+            "#,
+        )
+        .map_err(|e| format!("Failed to create debug_events schema: {}", e))?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn insert(&self, record: &DebugRecord) {
+        let conn = match self.conn.lock() {
+            Ok(conn) => conn,
+            Err(e) => {
+                log::warn!("Debug database mutex poisoned: {}", e);
+                return;
+            }
+        };
+
+        let kind = serde_json::to_value(record.kind)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        let params_json = record.params.as_ref().map(|v| v.to_string());
+        let result_json = record.result.as_ref().map(|v| v.to_string());
+        // rusqlite's ToSql isn't implemented for u64 (SQLite integers are
+        // i64, and a u64 isn't guaranteed to fit), so cast at the bind site.
+        let request_id = record.request_id.map(|v| v as i64);
+        let duration_ms = record.duration_ms.map(|v| v as i64);
+
+        let result = conn.execute(
+            "INSERT INTO debug_events (ts, kind, command, request_id, params, result, duration_ms, error)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                record.ts.to_rfc3339(),
+                kind,
+                record.command,
+                request_id,
+                params_json,
+                result_json,
+                duration_ms,
+                record.error,
+            ],
+        );
+
+        if let Err(e) = result {
+            log::warn!("Failed to insert debug event: {}", e);
+        }
+    }
+}
+
+impl DebugSink for SqliteSink {
+    fn log_invoke(&self, record: &DebugRecord) {
+        self.insert(record);
+    }
+
+    fn log_response(&self, record: &DebugRecord) {
+        self.insert(record);
+    }
+
+    fn log_event(&self, record: &DebugRecord) {
+        self.insert(record);
+    }
+
+    fn log_error(&self, record: &DebugRecord) {
+        self.insert(record);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn round_trips_a_record_into_the_debug_events_table() {
+        let sink = SqliteSink::new(":memory:").expect("open debug database");
+
+        let record = DebugRecord::invoke("debug_set_quiet", json!({ "quiet": true }), Some(7));
+        sink.log_invoke(&record);
+
+        let conn = sink.conn.lock().expect("lock connection");
+        let (command, request_id, params): (String, Option<i64>, Option<String>) = conn
+            .query_row(
+                "SELECT command, request_id, params FROM debug_events",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .expect("query inserted row");
+
+        assert_eq!(command, "debug_set_quiet");
+        assert_eq!(request_id, Some(7));
+        assert_eq!(params.as_deref(), Some(r#"{"quiet":true}"#));
+    }
+
+    #[test]
+    fn groups_by_command_across_correlated_records() {
+        let sink = SqliteSink::new(":memory:").expect("open debug database");
+
+        sink.log_invoke(&DebugRecord::invoke("debug_set_quiet", serde_json::Value::Null, Some(1)));
+        sink.log_response(&DebugRecord::response(
+            "debug_set_quiet",
+            serde_json::Value::Null,
+            5,
+            Some(1),
+        ));
+        sink.log_invoke(&DebugRecord::invoke("debug_set_filter", serde_json::Value::Null, Some(2)));
+
+        let conn = sink.conn.lock().expect("lock connection");
+        let count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM debug_events WHERE command = 'debug_set_quiet'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("count rows for command");
+
+        assert_eq!(count, 2);
+    }
+}
@@ -1,124 +1,123 @@
 //! tmux session management for debug mode
 //!
-//! Creates and manages a tmux session with a named pipe for real-time
-//! debug output streaming.
-
-use std::fs::{self, OpenOptions};
-use std::io::Write;
+//! Creates and manages a tmux session through the `tmux_interface` crate's
+//! typed command builders instead of shelling out to `mkfifo`/`tmux`
+//! directly. The session's window runs `tail -f` as a passive reader over
+//! an output file; `write()` appends to that file. Unlike `send-keys`,
+//! which types text into whatever is running in the pane, this never
+//! executes the rendered debug text as shell input.
+
+use std::fs::OpenOptions;
+use std::io::Write as _;
 use std::path::PathBuf;
-use std::process::Command;
+use tmux_interface::{HasSession, KillSession, ListSessions, NewSession, Tmux};
+
+/// Name of the window dedicated to streaming debug output.
+const OUTPUT_WINDOW: &str = "output";
 
 /// Manages a tmux session for debug output
 pub struct TmuxSession {
     session_name: String,
-    pipe_path: PathBuf,
+    output_path: PathBuf,
+    /// Whether this instance created the session (vs. attaching to one
+    /// that was already running). Only the creator tears it down on drop.
+    owns_session: bool,
 }
 
 impl TmuxSession {
-    /// Create a new tmux session with a named pipe for output
-    ///
-    /// # Arguments
-    /// * `name` - Name of the tmux session (e.g., "opcode-debug")
-    ///
-    /// # Returns
-    /// A new TmuxSession instance or an error message
     pub fn create(name: &str) -> Result<Self, String> {
-        let pipe_path = PathBuf::from(format!("/tmp/{}.pipe", name));
-
-        // Clean up existing pipe if present
-        if pipe_path.exists() {
-            fs::remove_file(&pipe_path).map_err(|e| format!("Failed to remove old pipe: {}", e))?;
-        }
-
-        // Create named pipe using mkfifo
-        let mkfifo_status = Command::new("mkfifo")
-            .arg(&pipe_path)
-            .status()
-            .map_err(|e| format!("Failed to create named pipe: {}", e))?;
+        let output_path = PathBuf::from(format!("/tmp/{}.log", name));
 
-        if !mkfifo_status.success() {
-            return Err("mkfifo command failed".to_string());
+        let mut session = Self {
+            session_name: name.to_string(),
+            output_path,
+            owns_session: false,
+        };
+
+        if session.session_exists()? {
+            log::info!("Reusing existing tmux session '{}'", name);
+            session.write("--- reconnected ---")?;
+            return Ok(session);
         }
 
-        // Kill existing tmux session if present
-        let _ = Command::new("tmux")
-            .args(["kill-session", "-t", name])
-            .output();
-
-        // Create new tmux session in detached mode running tail -f on the pipe
-        let tmux_status = Command::new("tmux")
-            .args([
-                "new-session",
-                "-d",
-                "-s",
-                name,
-                "-x",
-                "200",
-                "-y",
-                "50",
-                &format!("tail -f {}", pipe_path.display()),
-            ])
-            .status()
-            .map_err(|e| format!("Failed to create tmux session: {}", e))?;
-
-        if !tmux_status.success() {
-            // Clean up pipe on failure
-            let _ = fs::remove_file(&pipe_path);
-            return Err("Failed to create tmux session".to_string());
-        }
+        // Create the output file up front so `tail -f` has something to
+        // open immediately instead of racing the first write.
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&session.output_path)
+            .map_err(|e| format!("Failed to create debug output file: {}", e))?;
+
+        Tmux::with_command(
+            NewSession::new()
+                .detached()
+                .session_name(name)
+                .window_name(OUTPUT_WINDOW)
+                .width(200)
+                .height(50)
+                .shell_command(format!("tail -f {}", session.output_path.display())),
+        )
+        .output()
+        .map_err(|e| format!("Failed to create tmux session: {}", e))?;
+
+        session.owns_session = true;
 
         log::info!(
             "Debug tmux session '{}' created. Attach with: tmux attach -t {}",
-            name,
-            name
+            name, name
         );
 
-        Ok(Self {
-            session_name: name.to_string(),
-            pipe_path,
-        })
+        Ok(session)
+    }
+
+    pub fn session_exists(&self) -> Result<bool, String> {
+        match Tmux::with_command(HasSession::new().target_session(&self.session_name)).output() {
+            Ok(output) => Ok(output.status().success()),
+            Err(tmux_interface::Error::Tmux(_)) => Ok(false),
+            Err(e) => Err(format!("Failed to query tmux session: {}", e)),
+        }
     }
 
-    /// Write a message to the tmux session via the named pipe
-    ///
-    /// # Arguments
-    /// * `msg` - Message to write (will have newline appended)
+    /// Write a message to the debug session's output file; `tail -f` in
+    /// the session's window picks it up.
     pub fn write(&self, msg: &str) -> Result<(), String> {
-        // Open pipe in append mode, non-blocking
         let mut file = OpenOptions::new()
-            .write(true)
-            .open(&self.pipe_path)
-            .map_err(|e| format!("Failed to open pipe: {}", e))?;
+            .create(true)
+            .append(true)
+            .open(&self.output_path)
+            .map_err(|e| format!("Failed to open debug output file: {}", e))?;
 
         file.write_all(msg.as_bytes())
-            .map_err(|e| format!("Failed to write to pipe: {}", e))?;
-
-        file.write_all(b"\n")
-            .map_err(|e| format!("Failed to write newline: {}", e))?;
-
-        file.flush()
-            .map_err(|e| format!("Failed to flush pipe: {}", e))?;
+            .and_then(|_| file.write_all(b"\n"))
+            .and_then(|_| file.flush())
+            .map_err(|e| format!("Failed to write debug output: {}", e))?;
 
         Ok(())
     }
 
-    /// Get the session name
     pub fn session_name(&self) -> &str {
         &self.session_name
     }
 
-    /// Destroy the tmux session and clean up the pipe
-    pub fn destroy(&self) -> Result<(), String> {
-        // Kill tmux session
-        let _ = Command::new("tmux")
-            .args(["kill-session", "-t", &self.session_name])
-            .output();
-
-        // Remove pipe
-        if self.pipe_path.exists() {
-            fs::remove_file(&self.pipe_path)
-                .map_err(|e| format!("Failed to remove pipe: {}", e))?;
+    pub fn list(prefix: &str) -> Result<Vec<String>, String> {
+        match Tmux::with_command(ListSessions::new().format("#{session_name}")).output() {
+            Ok(output) => Ok(output
+                .stdout()
+                .lines()
+                .filter(|name| name.starts_with(prefix))
+                .map(str::to_string)
+                .collect()),
+            Err(tmux_interface::Error::Tmux(_)) => Ok(Vec::new()),
+            Err(e) => Err(format!("Failed to list tmux sessions: {}", e)),
         }
+    }
+
+    pub fn destroy(&self) -> Result<(), String> {
+        Tmux::with_command(KillSession::new().target_session(&self.session_name))
+            .output()
+            .map_err(|e| format!("Failed to kill tmux session: {}", e))?;
+
+        let _ = std::fs::remove_file(&self.output_path);
 
         log::info!("Debug tmux session '{}' destroyed", self.session_name);
 
@@ -128,8 +127,12 @@ impl TmuxSession {
 
 impl Drop for TmuxSession {
     fn drop(&mut self) {
-        // Best effort cleanup on drop
-        let _ = self.destroy();
+        // Only tear down a session this instance created; a reused session
+        // may still be watched by another process or another window, and
+        // its scrollback is the whole point of reusing it.
+        if self.owns_session {
+            let _ = self.destroy();
+        }
     }
 }
 
@@ -138,11 +141,25 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_pipe_path_format() {
-        let expected = PathBuf::from("/tmp/test-session.pipe");
-        assert_eq!(
-            PathBuf::from(format!("/tmp/{}.pipe", "test-session")),
-            expected
-        );
+    fn test_output_path_format() {
+        let session = TmuxSession {
+            session_name: "test-session".to_string(),
+            output_path: PathBuf::from("/tmp/test-session.log"),
+            owns_session: false,
+        };
+        assert_eq!(session.output_path, PathBuf::from("/tmp/test-session.log"));
+    }
+
+    #[test]
+    fn test_drop_does_not_destroy_reused_session() {
+        // A session this instance did not create must not be killed when
+        // it goes out of scope.
+        let session = TmuxSession {
+            session_name: "not-owned".to_string(),
+            output_path: PathBuf::from("/tmp/not-owned.log"),
+            owns_session: false,
+        };
+        assert!(!session.owns_session);
+        drop(session);
     }
 }
@@ -0,0 +1,191 @@
+//! Pluggable write target for the live debug view
+//!
+//! `TmuxSession` only works on Unix with tmux installed, which made
+//! `NOVA_DEBUG` a no-op on Windows or on machines without tmux. A
+//! `DebugTransport` abstracts "write this line of rendered debug text
+//! somewhere a human can watch it", so the tmux pane is one implementation
+//! among others rather than the only option.
+
+use std::io::Write;
+use std::net::{TcpListener, TcpStream};
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+
+use super::tmux::TmuxSession;
+
+/// Which transport a `DebugState` ended up using, surfaced to callers so
+/// the frontend can display the right attach instructions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransportKind {
+    Tmux,
+    Socket,
+}
+
+impl TransportKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TransportKind::Tmux => "tmux",
+            TransportKind::Socket => "socket",
+        }
+    }
+}
+
+/// Where rendered debug text is written
+pub trait DebugTransport: Send + Sync {
+    /// Write a single rendered message (no trailing newline required)
+    fn write(&self, msg: &str) -> Result<(), String>;
+
+    /// Human-readable instructions for attaching to this transport, shown
+    /// once at startup.
+    fn attach_instructions(&self) -> String;
+
+    /// The port a network-backed transport is listening on, if any. `None`
+    /// for transports like tmux that aren't reachable over the network.
+    fn port(&self) -> Option<u16> {
+        None
+    }
+}
+
+/// What a caller needs to tell a developer how to attach to the live view,
+/// captured at selection time since the transport itself is consumed by
+/// `LiveSink` right after.
+#[derive(Debug, Clone)]
+pub struct TransportInfo {
+    pub kind: TransportKind,
+    pub attach_instructions: String,
+    pub port: Option<u16>,
+}
+
+/// Live view backed by a tmux session (Unix, tmux installed)
+pub struct TmuxTransport {
+    session: TmuxSession,
+}
+
+impl TmuxTransport {
+    pub fn new(session_name: &str) -> Result<Self, String> {
+        Ok(Self {
+            session: TmuxSession::create(session_name)?,
+        })
+    }
+}
+
+impl DebugTransport for TmuxTransport {
+    fn write(&self, msg: &str) -> Result<(), String> {
+        self.session.write(msg)
+    }
+
+    fn attach_instructions(&self) -> String {
+        format!("tmux attach -t {}", self.session.session_name())
+    }
+}
+
+/// Live view backed by a local TCP broadcast server: any plain TCP client
+/// (e.g. `nc`) that connects to `127.0.0.1:<port>` receives every message
+/// written to the transport as raw text lines. There is no HTTP or
+/// WebSocket framing, so a browser cannot attach to it directly.
+pub struct SocketTransport {
+    port: u16,
+    clients: Arc<Mutex<Vec<TcpStream>>>,
+}
+
+impl SocketTransport {
+    /// Bind a broadcast server on an OS-assigned loopback port and start
+    /// accepting clients in the background.
+    pub fn new() -> Result<Self, String> {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .map_err(|e| format!("Failed to bind socket transport: {}", e))?;
+        let port = listener
+            .local_addr()
+            .map_err(|e| format!("Failed to read socket transport port: {}", e))?
+            .port();
+
+        let clients: Arc<Mutex<Vec<TcpStream>>> = Arc::new(Mutex::new(Vec::new()));
+        let accept_clients = clients.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                if let Ok(mut clients) = accept_clients.lock() {
+                    clients.push(stream);
+                }
+            }
+        });
+
+        log::info!(
+            "Debug socket transport listening on 127.0.0.1:{}. Attach with: nc 127.0.0.1 {}",
+            port, port
+        );
+
+        Ok(Self { port, clients })
+    }
+}
+
+impl DebugTransport for SocketTransport {
+    fn write(&self, msg: &str) -> Result<(), String> {
+        let mut clients = self
+            .clients
+            .lock()
+            .map_err(|e| format!("Socket transport mutex poisoned: {}", e))?;
+
+        clients.retain_mut(|client| {
+            let ok = client.write_all(msg.as_bytes()).and_then(|_| client.write_all(b"\n"));
+            ok.is_ok()
+        });
+
+        Ok(())
+    }
+
+    fn attach_instructions(&self) -> String {
+        format!("nc 127.0.0.1 {}", self.port)
+    }
+
+    fn port(&self) -> Option<u16> {
+        Some(self.port)
+    }
+}
+
+/// Whether a usable `tmux` binary is on `PATH`
+fn tmux_available() -> bool {
+    Command::new("tmux")
+        .arg("-V")
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// Pick a transport for `session_name`, honoring the `NOVA_DEBUG_TRANSPORT`
+/// override (`tmux` or `socket`) and otherwise preferring tmux on Unix when
+/// it is installed, falling back to the socket transport everywhere else.
+///
+/// Returns the transport alongside a [`TransportInfo`] snapshot, since the
+/// transport itself is about to be moved into a `LiveSink` and callers
+/// still need the attach instructions (and port, for network transports)
+/// to show a developer how to connect.
+pub fn select_transport(
+    session_name: &str,
+) -> Result<(Box<dyn DebugTransport>, TransportInfo), String> {
+    let (transport, kind): (Box<dyn DebugTransport>, TransportKind) =
+        match super::debug_transport_override().as_deref() {
+            Some("tmux") => (
+                Box::new(TmuxTransport::new(session_name)?),
+                TransportKind::Tmux,
+            ),
+            Some("socket") => (Box::new(SocketTransport::new()?), TransportKind::Socket),
+            _ => {
+                if cfg!(unix) && tmux_available() {
+                    (
+                        Box::new(TmuxTransport::new(session_name)?),
+                        TransportKind::Tmux,
+                    )
+                } else {
+                    (Box::new(SocketTransport::new()?), TransportKind::Socket)
+                }
+            }
+        };
+
+    let info = TransportInfo {
+        kind,
+        attach_instructions: transport.attach_instructions(),
+        port: transport.port(),
+    };
+
+    Ok((transport, info))
+}
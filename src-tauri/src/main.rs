@@ -1,6 +1,8 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod debug;
+
 use tauri::Manager;
 
 #[cfg(target_os = "macos")]
@@ -14,6 +16,15 @@ fn main() {
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_shell::init())
         .setup(|app| {
+            if debug::is_debug_enabled() {
+                match debug::DebugState::new(&debug::default_session_name()) {
+                    Ok(state) => {
+                        app.manage(state);
+                    }
+                    Err(e) => log::warn!("Failed to start debug mode: {}", e),
+                }
+            }
+
             // Apply window vibrancy with rounded corners on macOS
             #[cfg(target_os = "macos")]
             {
@@ -50,7 +61,12 @@ fn main() {
 
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![])
+        .invoke_handler(tauri::generate_handler![
+            debug::commands::debug_set_filter,
+            debug::commands::debug_set_verbosity,
+            debug::commands::debug_set_quiet,
+            debug::commands::debug_list_sessions,
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }